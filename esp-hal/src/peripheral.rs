@@ -62,6 +62,30 @@ impl<'a, T> PeripheralRef<'a, T> {
         // self, so user code can't use both at the same time.
         PeripheralRef::new(unsafe { self.inner.clone_unchecked() })
     }
+
+    /// Map the inner peripheral using `f`.
+    ///
+    /// This is useful for erasing a concrete peripheral's type, for example
+    /// turning a `PeripheralRef<'a, GPIO_5>` into a `PeripheralRef<'a,
+    /// AnyPin>`, while preserving the borrow that `self` represents.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PeripheralRef<'a, U> {
+        PeripheralRef {
+            inner: f(self.inner),
+            _lifetime: PhantomData,
+        }
+    }
+
+    /// Map the inner peripheral into `U` using its `Into` implementation.
+    ///
+    /// Shorthand for `self.map(Into::into)`.
+    #[inline]
+    pub fn map_into<U>(self) -> PeripheralRef<'a, U>
+    where
+        T: Into<U>,
+    {
+        self.map(Into::into)
+    }
 }
 
 impl<'a, T> Deref for PeripheralRef<'a, T> {
@@ -152,6 +176,37 @@ pub trait Peripheral: Sized + crate::private::Sealed {
     }
 }
 
+/// A peripheral singleton that occupies a slot in the
+/// [`Peripherals::release`]/[`Peripherals::try_take`] availability bitset.
+///
+/// This is implemented for every peripheral and interrupt handle generated by
+/// the `peripherals!` macro; each gets a unique [`PeripheralSlot::SLOT`] index.
+/// Peripherals are numbered first, in declaration order, followed by all
+/// interrupt handles in declaration order.
+pub trait PeripheralSlot: Peripheral<P = Self> + Sized {
+    /// Bit position of this peripheral within the availability bitset.
+    const SLOT: usize;
+
+    /// Unsafely create an instance of this peripheral out of thin air.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that you're only using one instance of this type at a time.
+    unsafe fn steal() -> Self;
+}
+
+/// Number of `u32` words backing the [`Peripherals::release`]/
+/// [`Peripherals::try_take`] availability bitset.
+///
+/// This bounds the number of distinct [`PeripheralSlot::SLOT`] indices that
+/// can be tracked; it comfortably covers every peripheral and interrupt
+/// handle on currently supported chips.
+pub(crate) const PERIPHERAL_SLOT_WORDS: usize = 8;
+
+#[doc(hidden)]
+pub static _ESP_HAL_PERIPHERAL_AVAILABLE: critical_section::Mutex<core::cell::Cell<[u32; PERIPHERAL_SLOT_WORDS]>> =
+    critical_section::Mutex::new(core::cell::Cell::new([0; PERIPHERAL_SLOT_WORDS]));
+
 impl<T, P> Peripheral for &mut T
 where
     T: Peripheral<P = P>,
@@ -169,7 +224,10 @@ mod peripheral_macros {
     #[doc(hidden)]
     #[macro_export]
     macro_rules! peripherals {
-        ($($(#[$cfg:meta])? $name:ident <= $from_pac:tt $(($($interrupt:ident),*))? ),*$(,)?) => {
+        (
+            $($(#[$cfg:meta])? $name:ident <= $from_pac:tt $(($($interrupt:ident),*))? ),*$(,)?
+            $(; late $late_mod:path : $($(#[$late_cfg:meta])? $late_name:ident),*$(,)?)?
+        ) => {
 
             /// Contains the generated peripherals which implement [`Peripheral`]
             mod peripherals {
@@ -177,6 +235,33 @@ mod peripheral_macros {
                 $(
                     $crate::create_peripheral!($(#[$cfg])? $name <= $from_pac);
                 )*
+                $(
+                    $(
+                        $(
+                            paste::paste!{
+                                $crate::create_interrupt_handle!([<$name _ $interrupt _INTERRUPT>], $interrupt);
+                            }
+                        )*
+                    )?
+                )*
+
+                // Each interrupt may be claimed by at most one peripheral's handle list:
+                // two peripherals declaring the same (shared/combined) interrupt would
+                // otherwise get independent handles that can both be bound at once,
+                // defeating the single-ownership guarantee this module provides. If two
+                // entries above share an `$interrupt`, this emits two `struct`s with the
+                // same name in the same module, failing the build with a duplicate
+                // definition error instead of allowing the double-bind at runtime.
+                #[allow(non_camel_case_types, dead_code)]
+                mod interrupt_claims {
+                    $(
+                        $(
+                            $(
+                                pub struct $interrupt;
+                            )*
+                        )?
+                    )*
+                }
             }
 
             /// The `Peripherals` struct provides access to all of the hardware peripherals on the chip.
@@ -187,6 +272,17 @@ mod peripheral_macros {
                     /// Each field represents a hardware peripheral.
                     pub $name: peripherals::$name,
                 )*
+                $(
+                    $(
+                        $(
+                            paste::paste!{
+                                $(#[$cfg])?
+                                /// Singleton handle granting exclusive ownership of this interrupt line.
+                                pub [<$name _ $interrupt _INTERRUPT>]: peripherals::[<$name _ $interrupt _INTERRUPT>],
+                            }
+                        )*
+                    )?
+                )*
             }
 
             impl Peripherals {
@@ -219,51 +315,82 @@ mod peripheral_macros {
                             $(#[$cfg])?
                             $name: peripherals::$name::steal(),
                         )*
+                        $(
+                            $(
+                                $(
+                                    paste::paste!{
+                                        $(#[$cfg])?
+                                        [<$name _ $interrupt _INTERRUPT>]: peripherals::[<$name _ $interrupt _INTERRUPT>]::steal(),
+                                    }
+                                )*
+                            )?
+                        )*
                     }
                 }
             }
 
+            impl Peripherals {
+                /// Release an owned peripheral singleton, marking its slot as
+                /// available for a later [`Peripherals::try_take`].
+                ///
+                /// This lets a driver that's done with a peripheral hand it back so a
+                /// different subsystem can claim it at runtime, without reaching for
+                /// `unsafe { steal() }`.
+                pub fn release<P: $crate::peripheral::PeripheralSlot>(_peripheral: P) {
+                    critical_section::with(|cs| {
+                        let mut bits = $crate::peripheral::_ESP_HAL_PERIPHERAL_AVAILABLE.borrow(cs).get();
+                        bits[P::SLOT / 32] |= 1 << (P::SLOT % 32);
+                        $crate::peripheral::_ESP_HAL_PERIPHERAL_AVAILABLE.borrow(cs).set(bits);
+                    });
+                }
+
+                /// Take a peripheral singleton, but only if it was previously handed
+                /// back via [`Peripherals::release`].
+                pub fn try_take<P: $crate::peripheral::PeripheralSlot>() -> Option<P> {
+                    critical_section::with(|cs| {
+                        let mut bits = $crate::peripheral::_ESP_HAL_PERIPHERAL_AVAILABLE.borrow(cs).get();
+                        let word = bits[P::SLOT / 32];
+                        if word & (1 << (P::SLOT % 32)) == 0 {
+                            return None;
+                        }
+                        bits[P::SLOT / 32] = word & !(1 << (P::SLOT % 32));
+                        $crate::peripheral::_ESP_HAL_PERIPHERAL_AVAILABLE.borrow(cs).set(bits);
+                        Some(unsafe { P::steal() })
+                    })
+                }
+            }
+
+            paste::paste!{
+                $crate::assign_peripheral_slots!(0usize; $($name,)* $($($([<$name _ $interrupt _INTERRUPT>],)*)?)*);
+            }
+
             #[allow(non_snake_case)]
             pub struct OptionalPeripherals {
                 $(
                     $(#[$cfg])?
                     pub $name: Option<peripherals::$name>,
                 )*
-                // These GPIO peripherals are intended to be populated later, when the `Io` type is
-                // instantiated, initializing GPIOs.
-                // We need to define them here so that users can access them like every other
-                // peripherals.
-                pub GPIO_0: Option<crate::gpio::GPIO_0>,
-                pub GPIO_1: Option<crate::gpio::GPIO_1>,
-                pub GPIO_2: Option<crate::gpio::GPIO_2>,
-                pub GPIO_3: Option<crate::gpio::GPIO_3>,
-                pub GPIO_4: Option<crate::gpio::GPIO_4>,
-                pub GPIO_5: Option<crate::gpio::GPIO_5>,
-                pub GPIO_6: Option<crate::gpio::GPIO_6>,
-                pub GPIO_7: Option<crate::gpio::GPIO_7>,
-                pub GPIO_8: Option<crate::gpio::GPIO_8>,
-                pub GPIO_9: Option<crate::gpio::GPIO_9>,
-                pub GPIO_10: Option<crate::gpio::GPIO_10>,
-                pub GPIO_11: Option<crate::gpio::GPIO_11>,
-                pub GPIO_12: Option<crate::gpio::GPIO_12>,
-                pub GPIO_13: Option<crate::gpio::GPIO_13>,
-                pub GPIO_14: Option<crate::gpio::GPIO_14>,
-                pub GPIO_15: Option<crate::gpio::GPIO_15>,
-                pub GPIO_16: Option<crate::gpio::GPIO_16>,
-                pub GPIO_17: Option<crate::gpio::GPIO_17>,
-                pub GPIO_18: Option<crate::gpio::GPIO_18>,
-                pub GPIO_19: Option<crate::gpio::GPIO_19>,
-                pub GPIO_20: Option<crate::gpio::GPIO_20>,
-                pub GPIO_21: Option<crate::gpio::GPIO_21>,
-                pub GPIO_22: Option<crate::gpio::GPIO_22>,
-                pub GPIO_23: Option<crate::gpio::GPIO_23>,
-                pub GPIO_24: Option<crate::gpio::GPIO_24>,
-                pub GPIO_25: Option<crate::gpio::GPIO_25>,
-                pub GPIO_26: Option<crate::gpio::GPIO_26>,
-                pub GPIO_27: Option<crate::gpio::GPIO_27>,
-                pub GPIO_28: Option<crate::gpio::GPIO_28>,
-                pub GPIO_29: Option<crate::gpio::GPIO_29>,
-                pub GPIO_30: Option<crate::gpio::GPIO_30>,
+                $(
+                    $(
+                        $(
+                            paste::paste!{
+                                $(#[$cfg])?
+                                pub [<$name _ $interrupt _INTERRUPT>]: Option<peripherals::[<$name _ $interrupt _INTERRUPT>]>,
+                            }
+                        )*
+                    )?
+                )*
+                // These peripherals are intended to be populated later, when e.g. the `Io`
+                // type is instantiated, initializing GPIOs. We need to define them here so
+                // that users can access them like every other peripheral. Declared via the
+                // `; late $mod: $name, ...` section of the `peripherals!` invocation so each
+                // chip only gets the late-initialized peripherals (e.g. GPIOs) it actually has.
+                $(
+                    $(
+                        $(#[$late_cfg])?
+                        pub $late_name: Option<$late_mod::$late_name>,
+                    )*
+                )?
             }
 
             impl OptionalPeripherals {
@@ -275,37 +402,22 @@ mod peripheral_macros {
                             $(#[$cfg])?
                             $name: Some(p.$name),
                         )*
-                        GPIO_0: None,
-                        GPIO_1: None,
-                        GPIO_2: None,
-                        GPIO_3: None,
-                        GPIO_4: None,
-                        GPIO_5: None,
-                        GPIO_6: None,
-                        GPIO_7: None,
-                        GPIO_8: None,
-                        GPIO_9: None,
-                        GPIO_10: None,
-                        GPIO_11: None,
-                        GPIO_12: None,
-                        GPIO_13: None,
-                        GPIO_14: None,
-                        GPIO_15: None,
-                        GPIO_16: None,
-                        GPIO_17: None,
-                        GPIO_18: None,
-                        GPIO_19: None,
-                        GPIO_20: None,
-                        GPIO_21: None,
-                        GPIO_22: None,
-                        GPIO_23: None,
-                        GPIO_24: None,
-                        GPIO_25: None,
-                        GPIO_26: None,
-                        GPIO_27: None,
-                        GPIO_28: None,
-                        GPIO_29: None,
-                        GPIO_30: None,
+                        $(
+                            $(
+                                $(
+                                    paste::paste!{
+                                        $(#[$cfg])?
+                                        [<$name _ $interrupt _INTERRUPT>]: Some(p.[<$name _ $interrupt _INTERRUPT>]),
+                                    }
+                                )*
+                            )?
+                        )*
+                        $(
+                            $(
+                                $(#[$late_cfg])?
+                                $late_name: None,
+                            )*
+                        )?
                     }
                 }
             }
@@ -314,25 +426,48 @@ mod peripheral_macros {
             $(
                 pub use peripherals::$name;
             )*
-
             $(
                 $(
-                    impl peripherals::$name {
-                        $(
-                            paste::paste!{
-                                /// Binds an interrupt handler to the corresponding interrupt for this peripheral.
-                                pub fn [<bind_ $interrupt:lower _interrupt >](&mut self, handler: unsafe extern "C" fn() -> ()) {
-                                    unsafe { $crate::interrupt::bind_interrupt($crate::peripherals::Interrupt::$interrupt, handler); }
-                                }
-                            }
-                        )*
-                    }
-                )*
+                    $(
+                        paste::paste!{
+                            pub use peripherals::[<$name _ $interrupt _INTERRUPT>];
+                        }
+                    )*
+                )?
             )*
 
         }
     }
 
+    #[doc(hidden)]
+    #[macro_export]
+    /// Assigns each peripheral/interrupt-handle singleton in `$name, ...` a unique,
+    /// incrementing [`$crate::peripheral::PeripheralSlot::SLOT`], starting at
+    /// `$counter`.
+    macro_rules! assign_peripheral_slots {
+        ($counter:expr;) => {
+            // Fails the build, instead of panicking at runtime in `Peripherals::release`/
+            // `try_take`, if this chip declares more peripherals/interrupt handles than
+            // the availability bitset has room for.
+            const _: () = assert!(
+                $counter <= $crate::peripheral::PERIPHERAL_SLOT_WORDS * 32,
+                "too many peripherals/interrupt handles for PERIPHERAL_SLOT_WORDS; increase PERIPHERAL_SLOT_WORDS in peripheral.rs"
+            );
+        };
+        ($counter:expr; $name:ident, $($rest:ident,)*) => {
+            impl $crate::peripheral::PeripheralSlot for peripherals::$name {
+                const SLOT: usize = $counter;
+
+                #[inline]
+                unsafe fn steal() -> Self {
+                    peripherals::$name::steal()
+                }
+            }
+
+            $crate::assign_peripheral_slots!($counter + 1; $($rest,)*);
+        };
+    }
+
     #[doc(hidden)]
     #[macro_export]
     macro_rules! into_ref {
@@ -441,4 +576,48 @@ mod peripheral_macros {
             impl $crate::private::Sealed for $name {}
         };
     }
+
+    #[doc(hidden)]
+    #[macro_export]
+    /// Macro to create an interrupt-handle singleton structure.
+    macro_rules! create_interrupt_handle {
+        ($name:ident, $interrupt:ident) => {
+            #[derive(Debug)]
+            #[allow(non_camel_case_types)]
+            /// Exclusive handle to a single interrupt line.
+            ///
+            /// This struct is generated by the `create_interrupt_handle!` macro. Owning
+            /// one of these is required to bind a handler for the interrupt, which
+            /// prevents two drivers from racing to claim the same line.
+            pub struct $name { _inner: () }
+
+            impl $name {
+                /// Unsafely create an instance of this interrupt handle out of thin air.
+                ///
+                /// # Safety
+                ///
+                /// You must ensure that you're only using one instance of this type at a time.
+                #[inline]
+                pub unsafe fn steal() -> Self {
+                    Self { _inner: () }
+                }
+
+                /// Binds an interrupt handler to this interrupt, consuming the handle.
+                pub fn bind(self, handler: unsafe extern "C" fn() -> ()) {
+                    unsafe { $crate::interrupt::bind_interrupt($crate::peripherals::Interrupt::$interrupt, handler); }
+                }
+            }
+
+            impl $crate::peripheral::Peripheral for $name {
+                type P = $name;
+
+                #[inline]
+                unsafe fn clone_unchecked(&mut self) -> Self::P {
+                    Self::steal()
+                }
+            }
+
+            impl $crate::private::Sealed for $name {}
+        };
+    }
 }